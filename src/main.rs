@@ -1,5 +1,5 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     env::current_exe,
     f32::consts::{PI, TAU},
     time::{Duration, Instant},
@@ -18,27 +18,253 @@ use winit::{
     dpi::PhysicalPosition,
     event::{ElementState, Event, KeyEvent, MouseButton, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
-    keyboard::{KeyCode, PhysicalKey},
+    keyboard::{KeyCode, ModifiersState, PhysicalKey},
 };
 
 const GRID_SIZE: u32 = 256;
 const SCALING: u32 = 8;
 const MAX_CHARGE: u32 = 16;
 
+const SNAPSHOT_MAGIC: &[u8; 4] = b"LLNS";
+const SNAPSHOT_VERSION: u32 = 2;
+
+struct Snapshot {
+    ground: Vec<bool>,
+    ground_source: Vec<bool>,
+    charge: Vec<u32>,
+    nearest_ground: Vec<[i32; 2]>,
+}
+
+fn save_snapshot(
+    path: &std::path::Path,
+    ground: &[bool],
+    ground_source: &[bool],
+    charge: &[u32],
+    nearest_ground: &[[i32; 2]],
+) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(SNAPSHOT_MAGIC)?;
+    file.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+    file.write_all(&GRID_SIZE.to_le_bytes())?;
+    file.write_all(&GRID_SIZE.to_le_bytes())?;
+    file.write_all(&MAX_CHARGE.to_le_bytes())?;
+    for &g in ground {
+        file.write_all(&[g as u8])?;
+    }
+    for &g in ground_source {
+        file.write_all(&[g as u8])?;
+    }
+    for &c in charge {
+        file.write_all(&c.to_le_bytes())?;
+    }
+    for &[x, y] in nearest_ground {
+        file.write_all(&x.to_le_bytes())?;
+        file.write_all(&y.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn load_snapshot(path: &std::path::Path) -> std::io::Result<Snapshot> {
+    use std::io::Read;
+    let mut file = std::fs::File::open(path)?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+
+    let mut cursor = 0usize;
+    macro_rules! take {
+        ($n:expr) => {{
+            match buf.get(cursor..cursor + $n) {
+                Some(slice) => {
+                    cursor += $n;
+                    slice
+                }
+                None => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "truncated snapshot",
+                    ));
+                }
+            }
+        }};
+    }
+    if take!(4) != SNAPSHOT_MAGIC {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "bad snapshot magic",
+        ));
+    }
+    let version = u32::from_le_bytes(take!(4).try_into().unwrap());
+    if version != SNAPSHOT_VERSION {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "unsupported snapshot version",
+        ));
+    }
+    let width = u32::from_le_bytes(take!(4).try_into().unwrap());
+    let height = u32::from_le_bytes(take!(4).try_into().unwrap());
+    let max_charge = u32::from_le_bytes(take!(4).try_into().unwrap());
+    if width != GRID_SIZE || height != GRID_SIZE || max_charge != MAX_CHARGE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "snapshot grid size or max charge does not match this build",
+        ));
+    }
+    let count = (width * height) as usize;
+    let ground = (0..count).map(|_| take!(1)[0] != 0).collect();
+    let ground_source = (0..count).map(|_| take!(1)[0] != 0).collect();
+    let charge = (0..count)
+        .map(|_| u32::from_le_bytes(take!(4).try_into().unwrap()))
+        .collect();
+    let nearest_ground = (0..count)
+        .map(|_| {
+            let x = i32::from_le_bytes(take!(4).try_into().unwrap());
+            let y = i32::from_le_bytes(take!(4).try_into().unwrap());
+            [x, y]
+        })
+        .collect();
+    Ok(Snapshot {
+        ground,
+        ground_source,
+        charge,
+        nearest_ground,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Action(&'static str);
+
+const PLACE_WALL: Action = Action("place_wall");
+const INJECT_CHARGE: Action = Action("inject_charge");
+const ERASE: Action = Action("erase");
+const SET_GROUND: Action = Action("set_ground");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Binding {
+    Mouse(MouseButton),
+    Key(KeyCode),
+}
+
+struct ActionMap {
+    bindings: HashMap<Binding, Action>,
+}
+
+impl ActionMap {
+    fn builder() -> ActionMapBuilder {
+        ActionMapBuilder {
+            bindings: HashMap::new(),
+        }
+    }
+    fn action_for(&self, binding: Binding) -> Option<Action> {
+        self.bindings.get(&binding).copied()
+    }
+}
+
+struct ActionMapBuilder {
+    bindings: HashMap<Binding, Action>,
+}
+impl ActionMapBuilder {
+    fn add_action(mut self, action: Action, binding: Binding) -> Self {
+        self.bindings.insert(binding, action);
+        self
+    }
+    fn build(self) -> ActionMap {
+        ActionMap {
+            bindings: self.bindings,
+        }
+    }
+}
+
+// grid_pos = display_pos / zoom + offset; zoom is display px per grid cell.
+#[derive(Debug, Clone, Copy)]
+struct Camera {
+    offset: Vec2<f32>,
+    zoom: f32,
+}
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::new(0.0, 0.0),
+            zoom: SCALING as f32,
+        }
+    }
+}
+
+fn grid_pos_under_cursor(cursor_pos: PhysicalPosition<f64>, camera: &Camera) -> Vec2<i32> {
+    let display_pos = Vec2::new(cursor_pos.x as f32, cursor_pos.y as f32);
+    let grid_pos = display_pos / camera.zoom + camera.offset;
+    Vec2::new(grid_pos.x.floor() as i32, grid_pos.y.floor() as i32)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RenderMode {
+    Classification,
+    ChargeHeatmap,
+    DistanceToGround,
+    FlowDirection,
+}
+impl RenderMode {
+    fn as_index(self) -> u32 {
+        match self {
+            RenderMode::Classification => 0,
+            RenderMode::ChargeHeatmap => 1,
+            RenderMode::DistanceToGround => 2,
+            RenderMode::FlowDirection => 3,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct Runtime {
     cursor_pos: PhysicalPosition<f64>,
     t: u32,
+    active_tool: Action,
+    brush_radius: i32,
+    paused: bool,
+    steps_per_frame: u32,
+    pending_step: u32,
+    modifiers: ModifiersState,
+    camera: Camera,
+    render_mode: RenderMode,
 }
 impl Default for Runtime {
     fn default() -> Self {
         Self {
             cursor_pos: PhysicalPosition::new(0.0, 0.0),
             t: 0,
+            active_tool: PLACE_WALL,
+            brush_radius: 0,
+            paused: false,
+            steps_per_frame: 1,
+            pending_step: 0,
+            modifiers: ModifiersState::empty(),
+            camera: Camera::default(),
+            render_mode: RenderMode::Classification,
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn dispatch_tool(
+    action: Action,
+    pos: Vec2<i32>,
+    radius: i32,
+    write_wall: &Kernel<fn(Vec2<i32>, i32)>,
+    write_charge: &Kernel<fn(Vec2<i32>, u32, i32)>,
+    erase_kernel: &Kernel<fn(Vec2<i32>, i32)>,
+    write_ground_source: &Kernel<fn(Vec2<i32>, i32)>,
+) {
+    if action == PLACE_WALL {
+        write_wall.dispatch_blocking(&pos, &radius);
+    } else if action == INJECT_CHARGE {
+        write_charge.dispatch_blocking(&pos, &MAX_CHARGE, &radius);
+    } else if action == ERASE {
+        erase_kernel.dispatch_blocking(&pos, &radius);
+    } else if action == SET_GROUND {
+        write_ground_source.dispatch_blocking(&pos, &radius);
+    }
+}
+
 fn main() {
     let _ = color_eyre::install();
     luisa::init_logger();
@@ -51,11 +277,11 @@ fn main() {
             GRID_SIZE * SCALING,
             GRID_SIZE * SCALING,
         ))
-        .with_resizable(false)
+        .with_resizable(true)
         .build(&event_loop)
         .unwrap();
 
-    let swapchain = device.create_swapchain(
+    let mut swapchain = device.create_swapchain(
         &window,
         &device.default_stream(),
         GRID_SIZE * SCALING,
@@ -64,22 +290,24 @@ fn main() {
         false,
         3,
     );
-    let display_texture = device.create_tex2d::<Vec4<f32>>(
+    let mut display_texture = device.create_tex2d::<Vec4<f32>>(
         swapchain.pixel_storage(),
         GRID_SIZE * SCALING,
         GRID_SIZE * SCALING,
         1,
     );
     let mut fields = FieldSet::new();
-    let display_domain = StaticDomain::<2>::new(GRID_SIZE * SCALING, GRID_SIZE * SCALING);
-    let display: VField<Vec4<f32>, Vec2<u32>> =
+    let mut display_domain = StaticDomain::<2>::new(GRID_SIZE * SCALING, GRID_SIZE * SCALING);
+    let mut display: VField<Vec4<f32>, Vec2<u32>> =
         fields.create_bind("display", display_domain.map_tex2d(display_texture.view(0)));
 
     let domain = GridDomain::new([0, 0], [GRID_SIZE; 2]);
 
+    let nearest_ground_texture =
+        device.create_tex2d::<Vec2<i32>>(PixelStorage::Int2, GRID_SIZE, GRID_SIZE, 1);
     let nearest_ground: VField<Vec2<i32>, Vec2<i32>> = fields.create_bind(
         "nearest-ground",
-        domain.map_texture(device.create_tex2d(PixelStorage::Int2, GRID_SIZE, GRID_SIZE, 1)),
+        domain.map_texture(nearest_ground_texture.clone()),
     );
     let valid: VField<bool, Vec2<i32>> = *fields.create_bind(
         "ground",
@@ -89,48 +317,101 @@ fn main() {
         "nearest-ground",
         domain.map_texture(device.create_tex2d(PixelStorage::Int2, GRID_SIZE, GRID_SIZE, 1)),
     );
+    let charge_buffer = device.create_buffer::<u32>(GRID_SIZE as usize * GRID_SIZE as usize);
     let charge: AField<u32, Vec2<i32>> = fields.create_bind(
         "charge",
-        domain.map_buffer_morton(device.create_buffer(GRID_SIZE as usize * GRID_SIZE as usize)),
+        domain.map_buffer_morton(charge_buffer.clone()),
     );
+    let next_charge_buffer = device.create_buffer::<u32>(GRID_SIZE as usize * GRID_SIZE as usize);
     let next_charge: AField<u32, Vec2<i32>> = fields.create_bind(
         "next_charge",
-        domain.map_buffer_morton(device.create_buffer(GRID_SIZE as usize * GRID_SIZE as usize)),
+        domain.map_buffer_morton(next_charge_buffer.clone()),
     );
 
+    let ground_buffer = device.create_buffer::<bool>(GRID_SIZE as usize * GRID_SIZE as usize);
     let ground: VField<bool, Vec2<i32>> = *fields.create_bind(
         "ground",
-        domain.map_buffer_morton(device.create_buffer(GRID_SIZE as usize * GRID_SIZE as usize)),
+        domain.map_buffer_morton(ground_buffer.clone()),
     );
 
-    let draw_kernel = Kernel::<fn()>::build(
-        &device,
-        &display_domain,
-        track!(&|mut display_el| {
-            let pos = (*display_el / SCALING).cast_i32();
-            let mut el = domain.index(pos, &display_el);
-            let color = if el.expr(&ground) {
-                Vec3::splat_expr(1.0_f32)
-            } else {
-                if el.expr(&charge) != 0 {
-                    Vec3::expr(0.5, 0.5, 0.0)
-                } else if el.expr(&valid) {
-                    Vec3::expr(0.0, 0.0, 0.2)
-                } else {
-                    Vec3::expr(0.0, 0.0, 0.0)
-                }
-                // let c = el.expr(&charge).cast_f32() / MAX_CHARGE as f32;
-                // Vec3::expr(1.0, 0.9, 0.2) * c
-            };
-            *display_el.var(&display) = color.extend(1.0);
-        }),
+    let ground_source_buffer = device.create_buffer::<bool>(GRID_SIZE as usize * GRID_SIZE as usize);
+    let ground_source: VField<bool, Vec2<i32>> = *fields.create_bind(
+        "ground_source",
+        domain.map_buffer_morton(ground_source_buffer.clone()),
     );
 
+    // Rebuilt on resize: both the dispatch domain and the display field point
+    // at a new texture, so the kernel has to be rebuilt against both.
+    let build_draw_kernel = |display_domain: &StaticDomain<2>, display: &VField<Vec4<f32>, Vec2<u32>>| {
+        Kernel::<fn(Vec2<f32>, f32, u32)>::build(
+            &device,
+            display_domain,
+            track!(&|mut display_el, offset, inv_zoom, mode| {
+                let grid_pos = ((*display_el).cast_f32() * inv_zoom + offset)
+                    .floor()
+                    .cast_i32();
+                if grid_pos.x < 0 {
+                    *display_el.var(display) = Vec4::splat_expr(0.0_f32);
+                    return;
+                }
+                if grid_pos.y < 0 {
+                    *display_el.var(display) = Vec4::splat_expr(0.0_f32);
+                    return;
+                }
+                if grid_pos.x >= GRID_SIZE as i32 {
+                    *display_el.var(display) = Vec4::splat_expr(0.0_f32);
+                    return;
+                }
+                if grid_pos.y >= GRID_SIZE as i32 {
+                    *display_el.var(display) = Vec4::splat_expr(0.0_f32);
+                    return;
+                }
+                let mut el = domain.index(grid_pos, &display_el);
+                let color = if mode == 1 {
+                    // Charge heatmap.
+                    let c = el.expr(&charge).cast_f32() / MAX_CHARGE as f32;
+                    Vec3::expr(1.0, 0.9, 0.2) * c
+                } else if mode == 2 {
+                    // Distance to the nearest ground cell, tonemapped.
+                    let delta = (el.expr(&nearest_ground) - grid_pos).cast_f32();
+                    let dist = (delta.x * delta.x + delta.y * delta.y).sqrt();
+                    let normalized = (dist / GRID_SIZE as f32).clamp(0.0, 1.0);
+                    Vec3::splat_expr(1.0 - (-normalized * 4.0).exp())
+                } else if mode == 3 {
+                    // Nearest-ground-finder flow direction, as hue from its angle.
+                    let delta = (el.expr(&nearest_ground_finder) - grid_pos).cast_f32();
+                    let hue = (delta.y.atan2(delta.x) + PI) / TAU;
+                    let hue6 = hue * 6.0;
+                    let r = ((hue6 - 3.0).abs() - 1.0).clamp(0.0, 1.0);
+                    let g = (2.0 - (hue6 - 2.0).abs()).clamp(0.0, 1.0);
+                    let b = (2.0 - (hue6 - 4.0).abs()).clamp(0.0, 1.0);
+                    Vec3::expr(r, g, b)
+                } else if el.expr(&ground_source) {
+                    Vec3::expr(0.0, 1.0, 0.0)
+                } else if el.expr(&ground) {
+                    Vec3::splat_expr(1.0_f32)
+                } else {
+                    if el.expr(&charge) != 0 {
+                        Vec3::expr(0.5, 0.5, 0.0)
+                    } else if el.expr(&valid) {
+                        Vec3::expr(0.0, 0.0, 0.2)
+                    } else {
+                        Vec3::expr(0.0, 0.0, 0.0)
+                    }
+                };
+                *display_el.var(display) = color.extend(1.0);
+            }),
+        )
+    };
+    let mut draw_kernel = build_draw_kernel(&display_domain, &display);
+
     let update_valid = Kernel::<fn()>::build(
         &device,
         &domain,
         track!(&|mut el| {
-            *el.var(&valid) = domain.index(el.expr(&nearest_ground), &el).expr(&ground);
+            *el.var(&valid) = domain
+                .index(el.expr(&nearest_ground), &el)
+                .expr(&ground_source);
         }),
     );
 
@@ -147,12 +428,18 @@ fn main() {
         &device,
         &domain,
         track!(&|mut el| {
+            if el.expr(&ground) {
+                return;
+            }
             let best_dist = i32::MAX.var();
             let best_ground = (*el).var();
             let old_ground_finder = el.expr(&nearest_ground_finder);
             let best_ground_finder = (*el).var();
             let pos = *el;
             domain.on_adjacent(&el, |mut el| {
+                if el.expr(&ground) {
+                    return;
+                }
                 let ground = el.expr(&nearest_ground);
                 let valid = el.expr(&valid);
                 if valid {
@@ -197,11 +484,11 @@ fn main() {
             if el.expr(&charge) == 0 {
                 return;
             }
-            if !el.expr(&valid) {
+            if el.expr(&ground_source) {
+                el.atomic(&next_charge).fetch_sub(1);
                 return;
             }
-            if el.expr(&ground) {
-                el.atomic(&next_charge).fetch_sub(1);
+            if !el.expr(&valid) {
                 return;
             }
             let finder = el.expr(&nearest_ground_finder);
@@ -223,44 +510,98 @@ fn main() {
         }),
     );
 
-    let write_wall = Kernel::<fn(Vec2<i32>)>::build(
+    let write_wall = Kernel::<fn(Vec2<i32>, i32)>::build(
         &device,
         &domain,
-        track!(&|mut el, pos| {
-            if (*el != pos).any() {
+        track!(&|mut el, pos, radius| {
+            let delta = *el - pos;
+            if delta.x.abs() > radius || delta.y.abs() > radius {
                 return;
             }
             *el.var(&ground) = true;
+            *el.var(&charge) = 0;
+            *el.var(&next_charge) = 0;
         }),
     );
-    let write_charge = Kernel::<fn(Vec2<i32>, u32)>::build(
+    let write_charge = Kernel::<fn(Vec2<i32>, u32, i32)>::build(
         &device,
         &domain,
-        track!(&|mut el, pos, c| {
-            if (*el != pos).any() {
+        track!(&|mut el, pos, c, radius| {
+            let delta = *el - pos;
+            if delta.x.abs() > radius || delta.y.abs() > radius {
+                return;
+            }
+            if el.expr(&ground) {
                 return;
             }
             *el.var(&charge) = c;
             *el.var(&next_charge) = c;
         }),
     );
+    let erase_kernel = Kernel::<fn(Vec2<i32>, i32)>::build(
+        &device,
+        &domain,
+        track!(&|mut el, pos, radius| {
+            let delta = *el - pos;
+            if delta.x.abs() > radius || delta.y.abs() > radius {
+                return;
+            }
+            *el.var(&ground) = false;
+            *el.var(&ground_source) = false;
+            *el.var(&charge) = 0;
+            *el.var(&next_charge) = 0;
+        }),
+    );
+    let write_ground_source = Kernel::<fn(Vec2<i32>, i32)>::build(
+        &device,
+        &domain,
+        track!(&|mut el, pos, radius| {
+            let delta = *el - pos;
+            if delta.x.abs() > radius || delta.y.abs() > radius {
+                return;
+            }
+            *el.var(&ground_source) = true;
+        }),
+    );
+    let clear_kernel = Kernel::<fn()>::build(
+        &device,
+        &domain,
+        track!(&|mut el| {
+            *el.var(&ground) = false;
+            *el.var(&ground_source) = false;
+            *el.var(&charge) = 0;
+            *el.var(&next_charge) = 0;
+        }),
+    );
 
     let mut graph = ComputeGraph::new(&device);
     graph.add((init_nearest_ground.dispatch(), update_valid.dispatch()).chain());
     graph.execute_clear();
 
+    let action_map = ActionMap::builder()
+        .add_action(PLACE_WALL, Binding::Mouse(MouseButton::Left))
+        .add_action(INJECT_CHARGE, Binding::Mouse(MouseButton::Right))
+        .add_action(ERASE, Binding::Key(KeyCode::KeyE))
+        .add_action(SET_GROUND, Binding::Key(KeyCode::KeyG))
+        .build();
+
     let mut active_buttons = HashSet::new();
 
     let mut update_cursor = |active_buttons: &HashSet<MouseButton>, rt: &mut Runtime| {
-        let pos = Vec2::new(
-            (rt.cursor_pos.x as i32) / SCALING as i32,
-            (rt.cursor_pos.y as i32) / SCALING as i32,
-        );
-        if active_buttons.contains(&MouseButton::Left) {
-            write_wall.dispatch_blocking(&pos);
-        }
-        if active_buttons.contains(&MouseButton::Right) {
-            write_charge.dispatch_blocking(&pos, &MAX_CHARGE);
+        let pos = grid_pos_under_cursor(rt.cursor_pos, &rt.camera);
+        for &button in active_buttons {
+            if let Some(action) = action_map.action_for(Binding::Mouse(button)) {
+                rt.active_tool = action;
+                dispatch_tool(
+                    action,
+                    pos,
+                    rt.brush_radius,
+                    &write_wall,
+                    &write_charge,
+                    &erase_kernel,
+                    &write_ground_source,
+                );
+            }
         }
     };
     let update_cursor = &mut update_cursor;
@@ -288,21 +629,66 @@ fn main() {
                     rt.t += 1;
                     update_cursor(&active_buttons, &mut rt);
 
-                    graph.add(
-                        (
-                            propegate_nearest.dispatch(),
-                            update_valid.dispatch(),
-                            discharge.dispatch(),
-                            copy_charge.dispatch(),
-                            draw_kernel.dispatch(),
-                        )
-                            .chain(),
-                    );
+                    let steps = if rt.paused {
+                        let steps = rt.pending_step;
+                        rt.pending_step = 0;
+                        steps
+                    } else {
+                        rt.steps_per_frame
+                    };
+                    for _ in 0..steps {
+                        graph.add(
+                            (
+                                propegate_nearest.dispatch(),
+                                update_valid.dispatch(),
+                                discharge.dispatch(),
+                                copy_charge.dispatch(),
+                            )
+                                .chain(),
+                        );
+                        graph.execute_clear();
+                    }
+                    graph.add(draw_kernel.dispatch(
+                        &rt.camera.offset,
+                        &(1.0 / rt.camera.zoom),
+                        &rt.render_mode.as_index(),
+                    ));
                     graph.execute_clear();
 
                     window.request_redraw();
                 }
+                WindowEvent::Resized(size) => {
+                    if size.width == 0 || size.height == 0 {
+                        return;
+                    }
+                    swapchain = device.create_swapchain(
+                        &window,
+                        &device.default_stream(),
+                        size.width,
+                        size.height,
+                        false,
+                        false,
+                        3,
+                    );
+                    display_texture = device.create_tex2d::<Vec4<f32>>(
+                        swapchain.pixel_storage(),
+                        size.width,
+                        size.height,
+                        1,
+                    );
+                    display_domain = StaticDomain::<2>::new(size.width, size.height);
+                    display = fields.create_bind(
+                        "display",
+                        display_domain.map_tex2d(display_texture.view(0)),
+                    );
+                    draw_kernel = build_draw_kernel(&display_domain, &display);
+                }
                 WindowEvent::CursorMoved { position, .. } => {
+                    if active_buttons.contains(&MouseButton::Middle) {
+                        let inv_zoom = 1.0 / rt.camera.zoom;
+                        rt.camera.offset.x -= (position.x - rt.cursor_pos.x) as f32 * inv_zoom;
+                        rt.camera.offset.y -= (position.y - rt.cursor_pos.y) as f32 * inv_zoom;
+                    }
                     rt.cursor_pos = position;
                     update_cursor(&active_buttons, &mut rt);
                 }
@@ -317,6 +703,150 @@ fn main() {
                     }
                     update_cursor(&active_buttons, &mut rt);
                 }
+                WindowEvent::MouseWheel { delta, .. } => {
+                    let scroll = match delta {
+                        winit::event::MouseScrollDelta::LineDelta(_, y) => y,
+                        winit::event::MouseScrollDelta::PixelDelta(pos) => (pos.y / 20.0) as f32,
+                    };
+                    let old_zoom = rt.camera.zoom;
+                    let new_zoom = (old_zoom * 1.15_f32.powf(scroll)).clamp(1.0, 64.0);
+                    let grid_under_cursor = grid_pos_under_cursor(rt.cursor_pos, &rt.camera);
+                    rt.camera.zoom = new_zoom;
+                    let cursor = Vec2::new(rt.cursor_pos.x as f32, rt.cursor_pos.y as f32);
+                    rt.camera.offset = Vec2::new(
+                        grid_under_cursor.x as f32 - cursor.x / new_zoom,
+                        grid_under_cursor.y as f32 - cursor.y / new_zoom,
+                    );
+                }
+                WindowEvent::ModifiersChanged(modifiers) => {
+                    rt.modifiers = modifiers.state();
+                }
+                WindowEvent::KeyboardInput { event, .. } => {
+                    if event.state == ElementState::Pressed && !event.repeat {
+                        if let PhysicalKey::Code(code) = event.physical_key {
+                            match code {
+                                KeyCode::BracketLeft => {
+                                    rt.brush_radius = (rt.brush_radius - 1).max(0);
+                                }
+                                KeyCode::BracketRight => {
+                                    rt.brush_radius += 1;
+                                }
+                                KeyCode::Space => {
+                                    rt.paused = !rt.paused;
+                                }
+                                KeyCode::Comma => {
+                                    rt.steps_per_frame = (rt.steps_per_frame / 2).max(1);
+                                }
+                                KeyCode::Period => {
+                                    rt.steps_per_frame *= 2;
+                                }
+                                KeyCode::KeyN => {
+                                    if rt.paused {
+                                        rt.pending_step += 1;
+                                    }
+                                }
+                                KeyCode::Digit1 => {
+                                    rt.render_mode = RenderMode::Classification;
+                                }
+                                KeyCode::Digit2 => {
+                                    rt.render_mode = RenderMode::ChargeHeatmap;
+                                }
+                                KeyCode::Digit3 => {
+                                    rt.render_mode = RenderMode::DistanceToGround;
+                                }
+                                KeyCode::Digit4 => {
+                                    rt.render_mode = RenderMode::FlowDirection;
+                                }
+                                KeyCode::KeyR => {
+                                    graph.add(
+                                        (
+                                            clear_kernel.dispatch(),
+                                            init_nearest_ground.dispatch(),
+                                            update_valid.dispatch(),
+                                        )
+                                            .chain(),
+                                    );
+                                    graph.execute_clear();
+                                }
+                                KeyCode::KeyS if rt.modifiers.control_key() => {
+                                    if let Some(path) = rfd::FileDialog::new()
+                                        .add_filter("Limbo Lightning Snapshot", &["lls"])
+                                        .set_file_name("scene.lls")
+                                        .save_file()
+                                    {
+                                        let ground_data = ground_buffer.view(..).copy_to_vec();
+                                        let ground_source_data =
+                                            ground_source_buffer.view(..).copy_to_vec();
+                                        let charge_data = charge_buffer.view(..).copy_to_vec();
+                                        let nearest_ground_data: Vec<[i32; 2]> =
+                                            nearest_ground_texture
+                                                .view(0)
+                                                .copy_to_vec()
+                                                .into_iter()
+                                                .map(|v: Vec2<i32>| [v.x, v.y])
+                                                .collect();
+                                        if let Err(err) = save_snapshot(
+                                            &path,
+                                            &ground_data,
+                                            &ground_source_data,
+                                            &charge_data,
+                                            &nearest_ground_data,
+                                        ) {
+                                            eprintln!("failed to save snapshot: {err}");
+                                        }
+                                    }
+                                }
+                                KeyCode::KeyO if rt.modifiers.control_key() => {
+                                    if let Some(path) = rfd::FileDialog::new()
+                                        .add_filter("Limbo Lightning Snapshot", &["lls"])
+                                        .pick_file()
+                                    {
+                                        match load_snapshot(&path) {
+                                            Ok(Snapshot {
+                                                ground,
+                                                ground_source,
+                                                charge,
+                                                ..
+                                            }) => {
+                                                ground_buffer.view(..).copy_from(&ground);
+                                                ground_source_buffer.view(..).copy_from(&ground_source);
+                                                charge_buffer.view(..).copy_from(&charge);
+                                                next_charge_buffer.view(..).copy_from(&charge);
+                                                graph.add(
+                                                    (
+                                                        init_nearest_ground.dispatch(),
+                                                        update_valid.dispatch(),
+                                                    )
+                                                        .chain(),
+                                                );
+                                                graph.execute_clear();
+                                            }
+                                            Err(err) => {
+                                                eprintln!("failed to load snapshot: {err}");
+                                            }
+                                        }
+                                    }
+                                }
+                                _ => {
+                                    if let Some(action) = action_map.action_for(Binding::Key(code))
+                                    {
+                                        rt.active_tool = action;
+                                        let pos = grid_pos_under_cursor(rt.cursor_pos, &rt.camera);
+                                        dispatch_tool(
+                                            action,
+                                            pos,
+                                            rt.brush_radius,
+                                            &write_wall,
+                                            &write_charge,
+                                            &erase_kernel,
+                                            &write_ground_source,
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
                 _ => (),
             },
             Event::AboutToWait => {